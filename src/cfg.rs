@@ -0,0 +1,170 @@
+//! Evaluation of `#[cfg(...)]` predicates against a caller-supplied
+//! environment, so that proc-macro authors can honor conditional
+//! compilation on the items they process.
+
+use syn::{AttrStyle, Attribute, Expr, ExprLit, Lit, Meta, MetaNameValue};
+
+use crate::iter_meta_list;
+
+/// Evaluates every `#[cfg(...)]` attribute in `attrs` against `is_set`,
+/// ANDing them together the way rustc does when several `#[cfg(..)]`
+/// attributes are attached to the same item. `is_set("feature", Some("foo"))`
+/// is queried for `feature = "foo"` leaves and `is_set("unix", None)` for bare
+/// path leaves. Returns `true` when there are no `#[cfg(..)]` attributes at
+/// all.
+pub fn eval_cfg(attrs: &[Attribute], is_set: impl Fn(&str, Option<&str>) -> bool) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer && attr.path().is_ident("cfg"))
+        .all(|attr| match &attr.meta {
+            Meta::List(meta_list) => {
+                iter_meta_list(meta_list, |iter| iter.all(|meta| eval_meta(meta, &is_set)))
+                    .unwrap_or(false)
+            }
+            _ => false,
+        })
+}
+
+fn eval_meta(meta: &Meta, is_set: &impl Fn(&str, Option<&str>) -> bool) -> bool {
+    match meta {
+        Meta::Path(path) => path
+            .get_ident()
+            .is_some_and(|ident| is_set(&ident.to_string(), None)),
+        Meta::NameValue(MetaNameValue {
+            path,
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }),
+            ..
+        }) => path
+            .get_ident()
+            .is_some_and(|ident| is_set(&ident.to_string(), Some(&lit_str.value()))),
+        Meta::NameValue(..) => false,
+        Meta::List(meta_list) => {
+            let combinator = meta_list.path.get_ident().map(|ident| ident.to_string());
+            iter_meta_list(meta_list, |iter| {
+                let metas: Vec<&Meta> = iter.collect();
+                match combinator.as_deref() {
+                    Some("all") => metas.iter().all(|meta| eval_meta(meta, is_set)),
+                    Some("any") => metas.iter().any(|meta| eval_meta(meta, is_set)),
+                    Some("not") => metas.len() == 1 && !eval_meta(metas[0], is_set),
+                    _ => false,
+                }
+            })
+            .unwrap_or(false)
+        }
+    }
+}
+
+/// Expands a `#[cfg_attr(predicate, attr1, attr2, ..)]` attribute into the
+/// inner attributes (`attr1`, `attr2`, ..) when `predicate` evaluates to
+/// `true` under `is_set`. Returns an empty `Vec` when `attr` isn't a
+/// `cfg_attr` attribute or when the predicate evaluates to `false`.
+pub fn expand_cfg_attr(
+    attr: &Attribute,
+    is_set: impl Fn(&str, Option<&str>) -> bool,
+) -> Vec<Attribute> {
+    if attr.style != AttrStyle::Outer || !attr.path().is_ident("cfg_attr") {
+        return vec![];
+    }
+
+    let Meta::List(meta_list) = &attr.meta else {
+        return vec![];
+    };
+
+    iter_meta_list(meta_list, |iter| {
+        let predicate = match iter.next() {
+            Some(meta) => meta,
+            None => return vec![],
+        };
+
+        if !eval_meta(predicate, &is_set) {
+            return vec![];
+        }
+
+        iter.cloned()
+            .map(|meta| Attribute {
+                pound_token: Default::default(),
+                style: AttrStyle::Outer,
+                bracket_token: Default::default(),
+                meta,
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::parse_quote;
+
+    fn is_set(key: &str, value: Option<&str>) -> bool {
+        matches!((key, value), ("unix", None) | ("feature", Some("foo")))
+    }
+
+    #[test]
+    fn test_eval_cfg_path() {
+        let attr: Attribute = parse_quote!(#[cfg(unix)]);
+        assert!(eval_cfg(&[attr], is_set));
+
+        let attr: Attribute = parse_quote!(#[cfg(windows)]);
+        assert!(!eval_cfg(&[attr], is_set));
+    }
+
+    #[test]
+    fn test_eval_cfg_name_value() {
+        let attr: Attribute = parse_quote!(#[cfg(feature = "foo")]);
+        assert!(eval_cfg(&[attr], is_set));
+
+        let attr: Attribute = parse_quote!(#[cfg(feature = "bar")]);
+        assert!(!eval_cfg(&[attr], is_set));
+    }
+
+    #[test]
+    fn test_eval_cfg_combinators() {
+        let attr: Attribute = parse_quote!(#[cfg(all(unix, feature = "foo"))]);
+        assert!(eval_cfg(&[attr], is_set));
+
+        let attr: Attribute = parse_quote!(#[cfg(all(unix, feature = "bar"))]);
+        assert!(!eval_cfg(&[attr], is_set));
+
+        let attr: Attribute = parse_quote!(#[cfg(any(windows, feature = "foo"))]);
+        assert!(eval_cfg(&[attr], is_set));
+
+        let attr: Attribute = parse_quote!(#[cfg(not(windows))]);
+        assert!(eval_cfg(&[attr], is_set));
+
+        let attr: Attribute = parse_quote!(#[cfg(not(unix))]);
+        assert!(!eval_cfg(&[attr], is_set));
+    }
+
+    #[test]
+    fn test_eval_cfg_multiple_attrs_are_anded() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[cfg(unix)]),
+            parse_quote!(#[cfg(feature = "bar")]),
+        ];
+        assert!(!eval_cfg(&attrs, is_set));
+    }
+
+    #[test]
+    fn test_eval_cfg_no_attrs() {
+        assert!(eval_cfg(&[], is_set));
+    }
+
+    #[test]
+    fn test_expand_cfg_attr() {
+        let attr: Attribute = parse_quote!(#[cfg_attr(unix, level0, level1 = "hi")]);
+
+        let expanded = expand_cfg_attr(&attr, is_set);
+        assert_eq!(expanded.len(), 2);
+        assert!(expanded[0].path().is_ident("level0"));
+        assert!(expanded[1].path().is_ident("level1"));
+
+        let attr: Attribute = parse_quote!(#[cfg_attr(windows, level0)]);
+        assert!(expand_cfg_attr(&attr, is_set).is_empty());
+    }
+}
@@ -1,11 +1,21 @@
+pub mod cfg;
 mod lit_cast;
+pub mod repr;
 
 use pmutil::ToTokensExt;
+use proc_macro2::Span;
 use std::collections::HashMap;
 use syn::punctuated::Punctuated;
-use syn::{AttrStyle, Attribute, Expr, ExprLit, Lit, Meta, MetaList, MetaNameValue, Result, Token};
+use syn::{
+    parse_quote, AttrStyle, Attribute, Expr, ExprLit, Ident, Lit, Meta, MetaList, MetaNameValue,
+    Result, Token,
+};
 
-use crate::lit_cast::FromLit;
+use crate::lit_cast::{FromLit, ToLit};
+
+fn is_inner_style(style: &AttrStyle) -> bool {
+    matches!(style, AttrStyle::Inner(..))
+}
 
 fn check_and_pop_hd<'a>(meta: &Meta, id: &'a [&'a str]) -> Option<&'a [&'a str]> {
     id.split_first().and_then(|(hd, tl)| {
@@ -17,7 +27,7 @@ fn check_and_pop_hd<'a>(meta: &Meta, id: &'a [&'a str]) -> Option<&'a [&'a str]>
     })
 }
 
-fn iter_meta_list<T, F>(meta_list: &MetaList, mut f: F) -> Result<T>
+pub(crate) fn iter_meta_list<T, F>(meta_list: &MetaList, mut f: F) -> Result<T>
 where
     F: FnMut(&mut syn::punctuated::Iter<Meta>) -> T,
 {
@@ -32,6 +42,15 @@ pub fn contains_attribute(attrs: &[Attribute], id: &[&str]) -> bool {
     })
 }
 
+/// Like [`contains_attribute`], but looks at inner attributes (`#![..]`)
+/// instead of outer ones, e.g. when scanning a `syn::File`'s or a module
+/// body's own attributes.
+pub fn contains_attribute_inner(attrs: &[Attribute], id: &[&str]) -> bool {
+    attrs.iter().any(|Attribute { style, meta, .. }| {
+        is_inner_style(style) && contains_attribute_impl(meta, id)
+    })
+}
+
 fn contains_attribute_impl(meta: &Meta, id: &[&str]) -> bool {
     let id = match check_and_pop_hd(meta, id) {
         Some(id) => id,
@@ -59,6 +78,17 @@ pub fn get_attribute_value<T: FromLit>(attrs: &[Attribute], id: &[&str]) -> Opti
     })
 }
 
+/// Like [`get_attribute_value`], but looks at inner attributes (`#![..]`)
+/// instead of outer ones.
+pub fn get_attribute_value_inner<T: FromLit>(attrs: &[Attribute], id: &[&str]) -> Option<T> {
+    attrs.iter().find_map(|Attribute { style, meta, .. }| {
+        if !is_inner_style(style) {
+            return None;
+        }
+        get_attribute_value_impl(meta, id).and_then(|value| T::from_lit(value).ok())
+    })
+}
+
 fn get_attribute_value_impl(meta: &Meta, id: &[&str]) -> Option<Lit> {
     let id = match check_and_pop_hd(meta, id) {
         Some(id) => id,
@@ -80,6 +110,149 @@ fn get_attribute_value_impl(meta: &Meta, id: &[&str]) -> Option<Lit> {
     }
 }
 
+/// Reads the leaf addressed by `id` as a comma-separated `Meta::List` of
+/// literals (e.g. `#[sizes(1, 2, 3)]`) and casts each element, for
+/// repeated/array-style attribute arguments that aren't nested attribute
+/// paths themselves. Elements that fail to cast are silently dropped, same
+/// as [`get_attribute_value`] does with its single value.
+pub fn get_attribute_values<T: FromLit>(attrs: &[Attribute], id: &[&str]) -> Vec<T> {
+    attrs
+        .iter()
+        .filter(|Attribute { style, .. }| *style == AttrStyle::Outer)
+        .find_map(|Attribute { meta, .. }| get_attribute_value_list_impl(meta, id))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|lit| T::from_lit(lit).ok())
+        .collect()
+}
+
+fn get_attribute_value_list_impl(meta: &Meta, id: &[&str]) -> Option<Vec<Lit>> {
+    let id = check_and_pop_hd(meta, id)?;
+
+    match meta {
+        Meta::List(meta_list) if id.is_empty() => meta_list
+            .parse_args_with(Punctuated::<Lit, Token![,]>::parse_terminated)
+            .ok()
+            .map(|lits| lits.into_iter().collect()),
+        Meta::List(meta_list) => iter_meta_list(meta_list, |iter| {
+            iter.find_map(|meta| get_attribute_value_list_impl(meta, id))
+        })
+        .unwrap_or(None),
+        Meta::Path(..) | Meta::NameValue(..) => None,
+    }
+}
+
+pub fn get_doc_comments(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer && attr.path().is_ident("doc"))
+        .filter_map(get_doc_comment)
+        .collect()
+}
+
+pub fn get_doc_string(attrs: &[Attribute]) -> String {
+    get_doc_comments(attrs).join("\n")
+}
+
+/// Like [`get_doc_comments`], but looks at inner doc comments (`//!`/`/*! .. */`)
+/// instead of outer ones, e.g. when collecting a module's or a `syn::File`'s
+/// own documentation.
+pub fn get_doc_comments_inner(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| is_inner_style(&attr.style) && attr.path().is_ident("doc"))
+        .filter_map(get_doc_comment)
+        .collect()
+}
+
+/// Like [`get_doc_string`], but looks at inner doc comments (`//!`/`/*! .. */`)
+/// instead of outer ones.
+pub fn get_doc_string_inner(attrs: &[Attribute]) -> String {
+    get_doc_comments_inner(attrs).join("\n")
+}
+
+fn get_doc_comment(attr: &Attribute) -> Option<String> {
+    match &attr.meta {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }),
+            ..
+        }) => Some(dedent_doc_literal(&lit_str.value())),
+        _ => None,
+    }
+}
+
+fn strip_one_leading_space(line: &str) -> &str {
+    line.strip_prefix(' ').unwrap_or(line)
+}
+
+// Mirrors rustdoc's normalization of `#[doc = "..."]` literals: single-line
+// entries (from `///`/`//!`) just lose one leading space, while block
+// entries (from `/** .. */`) are de-indented to the longest common
+// leading-whitespace prefix shared by their interior (non-blank) lines,
+// with the first line exempted since `/** text` carries no indentation of
+// its own, and a shared `*`-gutter column stripped if present.
+fn dedent_doc_literal(raw: &str) -> String {
+    if !raw.contains('\n') {
+        return strip_one_leading_space(raw).to_string();
+    }
+
+    let mut lines: Vec<&str> = raw.split('\n').collect();
+    let first = lines.remove(0);
+
+    let common_indent: &str = lines
+        .iter()
+        .copied()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .reduce(|common, indent| {
+            let len: usize = common
+                .chars()
+                .zip(indent.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.len_utf8())
+                .sum();
+            &common[..len]
+        })
+        .unwrap_or("");
+
+    let mut dedented: Vec<String> = lines
+        .into_iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                line[common_indent.len()..].to_string()
+            }
+        })
+        .collect();
+
+    if dedented
+        .iter()
+        .filter(|line| !line.is_empty())
+        .all(|line| line.starts_with('*'))
+    {
+        dedented = dedented
+            .into_iter()
+            .map(|line| {
+                if line.is_empty() {
+                    line
+                } else {
+                    strip_one_leading_space(&line[1..]).to_string()
+                }
+            })
+            .collect();
+    }
+
+    std::iter::once(strip_one_leading_space(first).to_string())
+        .chain(dedented)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub fn get_attribute_map(attrs: &[Attribute], separator: &str) -> HashMap<String, Vec<Lit>> {
     let mut result = HashMap::new();
     attrs.iter().for_each(|Attribute { style, meta, .. }| {
@@ -90,6 +263,18 @@ pub fn get_attribute_map(attrs: &[Attribute], separator: &str) -> HashMap<String
     result
 }
 
+/// Like [`get_attribute_map`], but looks at inner attributes (`#![..]`)
+/// instead of outer ones.
+pub fn get_attribute_map_inner(attrs: &[Attribute], separator: &str) -> HashMap<String, Vec<Lit>> {
+    let mut result = HashMap::new();
+    attrs.iter().for_each(|Attribute { style, meta, .. }| {
+        if is_inner_style(style) {
+            get_attribute_map_impl(&mut result, meta, "", separator);
+        }
+    });
+    result
+}
+
 fn get_attribute_map_impl(
     map: &mut HashMap<String, Vec<Lit>>,
     meta: &Meta,
@@ -130,6 +315,207 @@ fn get_attribute_map_impl(
     }
 }
 
+/// Sets the `a::b::c = value` leaf addressed by `id`, replacing it if it
+/// already exists or inserting it (creating any missing nesting) otherwise.
+/// A no-op for an empty `id`, same as [`contains_attribute`] and
+/// [`get_attribute_value`] treat an empty `id` as "no match".
+pub fn set_attribute_value<T: ToLit>(attrs: &mut Vec<Attribute>, id: &[&str], value: T) {
+    if id.is_empty() {
+        return;
+    }
+
+    let lit = value.to_lit();
+
+    for attr in attrs.iter_mut() {
+        if attr.style == AttrStyle::Outer {
+            if let Some(new_meta) = set_value_impl(&attr.meta, id, &lit) {
+                attr.meta = new_meta;
+                return;
+            }
+        }
+    }
+
+    attrs.push(build_attribute(id, lit));
+}
+
+fn set_value_impl(meta: &Meta, id: &[&str], lit: &Lit) -> Option<Meta> {
+    let tail_id = check_and_pop_hd(meta, id)?;
+    let path = meta.path();
+
+    if tail_id.is_empty() {
+        return Some(parse_quote!(#path = #lit));
+    }
+
+    match meta {
+        Meta::Path(..) => {
+            let child = build_meta(tail_id, lit.clone());
+            Some(parse_quote!(#path(#child)))
+        }
+        Meta::List(meta_list) => {
+            let mut nested: Vec<Meta> =
+                iter_meta_list(meta_list, |iter| iter.cloned().collect()).ok()?;
+
+            let replaced = nested.iter_mut().any(|child| {
+                set_value_impl(child, tail_id, lit)
+                    .map(|new_child| *child = new_child)
+                    .is_some()
+            });
+            if !replaced {
+                nested.push(build_meta(tail_id, lit.clone()));
+            }
+
+            Some(parse_quote!(#path(#(#nested),*)))
+        }
+        Meta::NameValue(..) => None,
+    }
+}
+
+/// Appends `a::b::c = value` as a new, additional leaf under `id`'s parent,
+/// mirroring how [`get_attribute_map`] collects repeated keys into a
+/// `Vec<Lit>` instead of overwriting them. A no-op for an empty `id`.
+pub fn push_attribute_value<T: ToLit>(attrs: &mut Vec<Attribute>, id: &[&str], value: T) {
+    let Some((leaf_key, parent_id)) = id.split_last() else {
+        return;
+    };
+
+    let lit = value.to_lit();
+
+    if parent_id.is_empty() {
+        attrs.push(build_attribute(id, lit));
+        return;
+    }
+
+    for attr in attrs.iter_mut() {
+        if attr.style == AttrStyle::Outer {
+            if let Some(new_meta) = push_value_impl(&attr.meta, parent_id, leaf_key, &lit) {
+                attr.meta = new_meta;
+                return;
+            }
+        }
+    }
+
+    attrs.push(build_attribute(id, lit));
+}
+
+fn push_value_impl(meta: &Meta, parent_id: &[&str], leaf_key: &str, lit: &Lit) -> Option<Meta> {
+    let tail_id = check_and_pop_hd(meta, parent_id)?;
+    let path = meta.path();
+
+    if tail_id.is_empty() {
+        let leaf = build_meta(&[leaf_key], lit.clone());
+        return Some(match meta {
+            Meta::Path(..) => parse_quote!(#path(#leaf)),
+            Meta::List(meta_list) => {
+                let mut nested: Vec<Meta> =
+                    iter_meta_list(meta_list, |iter| iter.cloned().collect()).ok()?;
+                nested.push(leaf);
+                parse_quote!(#path(#(#nested),*))
+            }
+            Meta::NameValue(..) => return None,
+        });
+    }
+
+    match meta {
+        Meta::List(meta_list) => {
+            let mut nested: Vec<Meta> =
+                iter_meta_list(meta_list, |iter| iter.cloned().collect()).ok()?;
+
+            let updated = nested.iter_mut().any(|child| {
+                push_value_impl(child, tail_id, leaf_key, lit)
+                    .map(|new_child| *child = new_child)
+                    .is_some()
+            });
+            if !updated {
+                let nested_id: Vec<&str> = tail_id.iter().copied().chain([leaf_key]).collect();
+                nested.push(build_meta(&nested_id, lit.clone()));
+            }
+
+            Some(parse_quote!(#path(#(#nested),*)))
+        }
+        _ => None,
+    }
+}
+
+/// Removes the leaf addressed by `id`, pruning any `MetaList` parent that
+/// becomes empty as a result. Returns whether a matching leaf was found.
+pub fn remove_attribute(attrs: &mut Vec<Attribute>, id: &[&str]) -> bool {
+    for i in 0..attrs.len() {
+        if attrs[i].style != AttrStyle::Outer {
+            continue;
+        }
+
+        match remove_value_impl(&attrs[i].meta, id) {
+            Some(None) => {
+                attrs.remove(i);
+                return true;
+            }
+            Some(Some(new_meta)) => {
+                attrs[i].meta = new_meta;
+                return true;
+            }
+            None => {}
+        }
+    }
+
+    false
+}
+
+// `None` means no match was found; `Some(None)` means a match was found and
+// this node should be pruned entirely; `Some(Some(meta))` means a match was
+// found and `meta` is the updated replacement.
+fn remove_value_impl(meta: &Meta, id: &[&str]) -> Option<Option<Meta>> {
+    let tail_id = check_and_pop_hd(meta, id)?;
+
+    if tail_id.is_empty() {
+        return Some(None);
+    }
+
+    match meta {
+        Meta::List(meta_list) => {
+            let path = meta.path();
+            let mut nested: Vec<Meta> =
+                iter_meta_list(meta_list, |iter| iter.cloned().collect()).ok()?;
+
+            for i in 0..nested.len() {
+                match remove_value_impl(&nested[i], tail_id) {
+                    Some(None) => {
+                        nested.remove(i);
+                        return Some(if nested.is_empty() {
+                            None
+                        } else {
+                            Some(parse_quote!(#path(#(#nested),*)))
+                        });
+                    }
+                    Some(Some(new_child)) => {
+                        nested[i] = new_child;
+                        return Some(Some(parse_quote!(#path(#(#nested),*))));
+                    }
+                    None => {}
+                }
+            }
+
+            None
+        }
+        _ => None,
+    }
+}
+
+fn build_meta(id: &[&str], lit: Lit) -> Meta {
+    let (hd, tl) = id.split_first().expect("id must not be empty");
+    let ident = Ident::new(hd, Span::call_site());
+    if tl.is_empty() {
+        parse_quote!(#ident = #lit)
+    } else {
+        let inner = build_meta(tl, lit);
+        parse_quote!(#ident(#inner))
+    }
+}
+
+fn build_attribute(id: &[&str], lit: Lit) -> Attribute {
+    let meta = build_meta(id, lit);
+    parse_quote!(#[#meta])
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -173,6 +559,15 @@ mod test {
         ),);
     }
 
+    #[test]
+    fn test_contains_attribute_inner() {
+        let attr: Attribute = parse_quote!(#![level0]);
+        assert!(contains_attribute_inner(&[attr], &["level0"]));
+
+        let attr: Attribute = parse_quote!(#[level0]);
+        assert!(!contains_attribute_inner(&[attr], &["level0"]));
+    }
+
     #[test]
     fn test_get_attribute_value_impl() {
         let attr: Attribute = parse_quote!(#[level0(level1 = "hi", level1_1(level2 = "bye"))]);
@@ -243,6 +638,42 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_get_attribute_values() {
+        let attr: Attribute = parse_quote!(#[sizes(1, 2, 3)]);
+        assert_eq!(
+            get_attribute_values::<u64>(&[attr], &["sizes"]),
+            vec![1, 2, 3]
+        );
+
+        let attr: Attribute = parse_quote!(#[level0(sizes(1, 2, 3))]);
+        assert_eq!(
+            get_attribute_values::<u64>(&[attr], &["level0", "sizes"]),
+            vec![1, 2, 3]
+        );
+
+        let attr: Attribute = parse_quote!(#[level0 = "hi"]);
+        assert_eq!(
+            get_attribute_values::<u64>(&[attr], &["level0"]),
+            Vec::<u64>::new()
+        );
+
+        let attr: Attribute = parse_quote!(#[sizes(1, "nope", 3)]);
+        assert_eq!(get_attribute_values::<u64>(&[attr], &["sizes"]), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_get_attribute_value_inner() {
+        let attr: Attribute = parse_quote!(#![level0 = "hi"]);
+        assert_eq!(
+            get_attribute_value_inner(&[attr], &["level0"]),
+            Some(lit_str("hi"))
+        );
+
+        let attr: Attribute = parse_quote!(#[level0 = "hi"]);
+        assert_eq!(get_attribute_value_inner::<Lit>(&[attr], &["level0"]), None);
+    }
+
     #[test]
     fn test_get_attribute_map_impl() {
         let attr: Attribute =
@@ -266,6 +697,49 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_get_doc_comments_line_style() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[doc = " hello"]),
+            parse_quote!(#[doc = " world"]),
+        ];
+
+        assert_eq!(get_doc_comments(&attrs), vec!["hello", "world"]);
+        assert_eq!(get_doc_string(&attrs), "hello\nworld");
+    }
+
+    #[test]
+    fn test_get_doc_comments_ignores_non_doc_attrs() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[doc = " hello"]), parse_quote!(#[level0])];
+
+        assert_eq!(get_doc_comments(&attrs), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_get_doc_comments_block_style() {
+        let attr: Attribute = parse_quote!(#[doc = " block\n     indented\n     more"]);
+
+        assert_eq!(get_doc_comments(&[attr]), vec!["block\nindented\nmore"]);
+    }
+
+    #[test]
+    fn test_get_doc_comments_block_style_with_gutter() {
+        let attr: Attribute = parse_quote!(#[doc = " block\n     * indented\n     * more"]);
+
+        assert_eq!(get_doc_comments(&[attr]), vec!["block\nindented\nmore"]);
+    }
+
+    #[test]
+    fn test_get_doc_comments_inner() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#![doc = " hello"]),
+            parse_quote!(#[doc = " world"]),
+        ];
+
+        assert_eq!(get_doc_comments_inner(&attrs), vec!["hello"]);
+        assert_eq!(get_doc_string_inner(&attrs), "hello");
+    }
+
     #[test]
     fn test_get_attribute_map() {
         assert_eq!(
@@ -300,4 +774,126 @@ mod test {
             .collect()
         );
     }
+
+    #[test]
+    fn test_get_attribute_map_inner() {
+        assert_eq!(
+            get_attribute_map_inner(
+                &[
+                    parse_quote!(#![level9]),
+                    parse_quote!(#[level0_0 = "greeting"]),
+                ],
+                ".",
+            ),
+            vec![("level9".to_string(), vec![])].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_set_attribute_value_replaces_existing() {
+        let mut attrs: Vec<Attribute> =
+            vec![parse_quote!(#[level0(level1 = "hi", level1_1(level2 = "bye"))])];
+
+        set_attribute_value(&mut attrs, &["level0", "level1_1", "level2"], "hello");
+
+        assert_eq!(
+            get_attribute_value::<String>(&attrs, &["level0", "level1_1", "level2"]),
+            Some("hello".to_string())
+        );
+        assert_eq!(
+            get_attribute_value::<String>(&attrs, &["level0", "level1"]),
+            Some("hi".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_attribute_value_inserts_missing() {
+        let mut attrs: Vec<Attribute> = vec![parse_quote!(#[level0(level1 = "hi")])];
+
+        set_attribute_value(&mut attrs, &["level0", "level1_1", "level2"], "bye");
+
+        assert_eq!(
+            get_attribute_value::<String>(&attrs, &["level0", "level1_1", "level2"]),
+            Some("bye".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_attribute_value_new_attribute() {
+        let mut attrs: Vec<Attribute> = vec![];
+
+        set_attribute_value(&mut attrs, &["level0", "level1"], 42u64);
+
+        assert_eq!(
+            get_attribute_value::<u64>(&attrs, &["level0", "level1"]),
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn test_set_attribute_value_empty_id_is_a_no_op() {
+        let mut attrs: Vec<Attribute> = vec![parse_quote!(#[level0 = "hi"])];
+
+        set_attribute_value(&mut attrs, &[], "bye");
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(
+            get_attribute_value::<String>(&attrs, &["level0"]),
+            Some("hi".to_string())
+        );
+
+        let mut attrs: Vec<Attribute> = vec![];
+        set_attribute_value(&mut attrs, &[], "bye");
+        assert!(attrs.is_empty());
+    }
+
+    #[test]
+    fn test_push_attribute_value() {
+        let mut attrs: Vec<Attribute> = vec![parse_quote!(#[level0(level1 = "hi")])];
+
+        push_attribute_value(&mut attrs, &["level0", "level1"], "bye");
+
+        assert_eq!(
+            get_attribute_map(&attrs, ".").get("level0.level1"),
+            Some(&vec![lit_str("hi"), lit_str("bye")])
+        );
+    }
+
+    #[test]
+    fn test_push_attribute_value_empty_id_is_a_no_op() {
+        let mut attrs: Vec<Attribute> = vec![parse_quote!(#[level0 = "hi"])];
+
+        push_attribute_value(&mut attrs, &[], "bye");
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(
+            get_attribute_map(&attrs, ".").get("level0"),
+            Some(&vec![lit_str("hi")])
+        );
+
+        let mut attrs: Vec<Attribute> = vec![];
+        push_attribute_value(&mut attrs, &[], "bye");
+        assert!(attrs.is_empty());
+    }
+
+    #[test]
+    fn test_remove_attribute() {
+        let mut attrs: Vec<Attribute> =
+            vec![parse_quote!(#[level0(level1 = "hi", level1_1(level2 = "bye"))])];
+
+        assert!(remove_attribute(
+            &mut attrs,
+            &["level0", "level1_1", "level2"]
+        ));
+        assert!(!contains_attribute(&attrs, &["level0", "level1_1"]));
+        assert_eq!(
+            get_attribute_value::<String>(&attrs, &["level0", "level1"]),
+            Some("hi".to_string())
+        );
+
+        assert!(remove_attribute(&mut attrs, &["level0", "level1"]));
+        assert!(attrs.is_empty());
+
+        assert!(!remove_attribute(&mut attrs, &["level0", "level1"]));
+    }
 }
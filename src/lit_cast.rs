@@ -1,50 +1,140 @@
-use syn::Lit;
+use proc_macro2::Span;
+use syn::{Lit, LitBool, LitFloat, LitInt, LitStr};
 
-#[derive(Debug, PartialEq)]
-pub struct CastError;
+#[derive(Debug)]
+pub struct CastError {
+    span: Span,
+}
+
+impl CastError {
+    fn new(span: Span) -> Self {
+        CastError { span }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
 
 pub trait FromLit: Sized {
     fn from_lit(lit: Lit) -> Result<Self, CastError>;
 }
 
+/// The inverse of [`FromLit`]: turns a plain value into the `syn::Lit` an
+/// attribute leaf would carry, so that writers don't have to construct
+/// `syn::Lit` variants by hand.
+pub trait ToLit {
+    fn to_lit(self) -> Lit;
+}
+
+impl ToLit for Lit {
+    fn to_lit(self) -> Lit {
+        self
+    }
+}
+
+impl ToLit for u64 {
+    fn to_lit(self) -> Lit {
+        Lit::Int(LitInt::new(&self.to_string(), Span::call_site()))
+    }
+}
+
+impl ToLit for f64 {
+    fn to_lit(self) -> Lit {
+        Lit::Float(LitFloat::new(&self.to_string(), Span::call_site()))
+    }
+}
+
+impl ToLit for bool {
+    fn to_lit(self) -> Lit {
+        Lit::Bool(LitBool::new(self, Span::call_site()))
+    }
+}
+
+impl ToLit for String {
+    fn to_lit(self) -> Lit {
+        Lit::Str(LitStr::new(&self, Span::call_site()))
+    }
+}
+
+impl ToLit for &str {
+    fn to_lit(self) -> Lit {
+        Lit::Str(LitStr::new(self, Span::call_site()))
+    }
+}
+
 impl FromLit for Lit {
     fn from_lit(lit: Lit) -> Result<Self, CastError> {
         Ok(lit)
     }
 }
 
-impl FromLit for u64 {
+macro_rules! impl_from_lit_int {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl FromLit for $ty {
+                fn from_lit(lit: Lit) -> Result<Self, CastError> {
+                    match &lit {
+                        Lit::Int(int) => int.base10_parse().map_err(|_| CastError::new(lit.span())),
+                        _ => Err(CastError::new(lit.span())),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_lit_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+macro_rules! impl_from_lit_float {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl FromLit for $ty {
+                fn from_lit(lit: Lit) -> Result<Self, CastError> {
+                    match &lit {
+                        Lit::Float(float) => float.base10_parse().map_err(|_| CastError::new(lit.span())),
+                        _ => Err(CastError::new(lit.span())),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_lit_float!(f32, f64);
+
+impl FromLit for bool {
     fn from_lit(lit: Lit) -> Result<Self, CastError> {
-        match lit {
-            Lit::Int(int) => int.base10_parse().map_err(|_| CastError),
-            _ => Err(CastError),
+        match &lit {
+            Lit::Bool(bool_lit) => Ok(bool_lit.value),
+            _ => Err(CastError::new(lit.span())),
         }
     }
 }
 
-impl FromLit for f64 {
+impl FromLit for String {
     fn from_lit(lit: Lit) -> Result<Self, CastError> {
-        match lit {
-            Lit::Float(float) => float.base10_parse().map_err(|_| CastError),
-            _ => Err(CastError),
+        match &lit {
+            Lit::Str(string) => Ok(string.value()),
+            _ => Err(CastError::new(lit.span())),
         }
     }
 }
 
-impl FromLit for bool {
+impl FromLit for char {
     fn from_lit(lit: Lit) -> Result<Self, CastError> {
-        match lit {
-            Lit::Bool(lit) => Ok(lit.value),
-            _ => Err(CastError),
+        match &lit {
+            Lit::Char(char_lit) => Ok(char_lit.value()),
+            _ => Err(CastError::new(lit.span())),
         }
     }
 }
 
-impl FromLit for String {
+impl FromLit for Vec<u8> {
     fn from_lit(lit: Lit) -> Result<Self, CastError> {
-        match lit {
-            Lit::Str(string) => Ok(string.value()),
-            _ => Err(CastError),
+        match &lit {
+            Lit::ByteStr(byte_str) => Ok(byte_str.value()),
+            _ => Err(CastError::new(lit.span())),
         }
     }
 }
@@ -61,10 +151,32 @@ mod test {
         let float_lit: Lit = parse_quote!(12.1);
         let bool_lit: Lit = parse_quote!(false);
 
-        assert_eq!(Ok(12), u64::from_lit(int_lit));
-        assert_eq!(Err(CastError), u64::from_lit(str_lit));
-        assert_eq!(Err(CastError), u64::from_lit(float_lit));
-        assert_eq!(Err(CastError), u64::from_lit(bool_lit));
+        assert_eq!(u64::from_lit(int_lit).unwrap(), 12);
+        assert!(u64::from_lit(str_lit).is_err());
+        assert!(u64::from_lit(float_lit).is_err());
+        assert!(u64::from_lit(bool_lit).is_err());
+    }
+
+    #[test]
+    fn test_int_widths() {
+        let int_lit: Lit = parse_quote!(12);
+
+        assert_eq!(i8::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(i16::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(i32::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(i64::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(u8::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(u16::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(u32::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(usize::from_lit(int_lit.clone()).unwrap(), 12);
+        assert_eq!(isize::from_lit(int_lit).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_int_overflow_is_a_cast_error() {
+        let too_big: Lit = parse_quote!(1000);
+
+        assert!(u8::from_lit(too_big).is_err());
     }
 
     #[test]
@@ -74,10 +186,10 @@ mod test {
         let float_lit: Lit = parse_quote!(12.1);
         let bool_lit: Lit = parse_quote!(false);
 
-        assert_eq!(Err(CastError), String::from_lit(int_lit));
-        assert_eq!(Ok("str".to_string()), String::from_lit(str_lit));
-        assert_eq!(Err(CastError), String::from_lit(float_lit));
-        assert_eq!(Err(CastError), String::from_lit(bool_lit));
+        assert!(String::from_lit(int_lit).is_err());
+        assert_eq!(String::from_lit(str_lit).unwrap(), "str");
+        assert!(String::from_lit(float_lit).is_err());
+        assert!(String::from_lit(bool_lit).is_err());
     }
 
     #[test]
@@ -87,10 +199,11 @@ mod test {
         let float_lit: Lit = parse_quote!(12.1);
         let bool_lit: Lit = parse_quote!(false);
 
-        assert_eq!(Err(CastError), f64::from_lit(int_lit));
-        assert_eq!(Err(CastError), f64::from_lit(str_lit));
-        assert_eq!(Ok(12.1), f64::from_lit(float_lit));
-        assert_eq!(Err(CastError), f64::from_lit(bool_lit));
+        assert!(f64::from_lit(int_lit).is_err());
+        assert!(f64::from_lit(str_lit).is_err());
+        assert_eq!(f64::from_lit(float_lit.clone()).unwrap(), 12.1);
+        assert_eq!(f32::from_lit(float_lit).unwrap(), 12.1);
+        assert!(f64::from_lit(bool_lit).is_err());
     }
 
     #[test]
@@ -100,9 +213,41 @@ mod test {
         let float_lit: Lit = parse_quote!(12.1);
         let bool_lit: Lit = parse_quote!(false);
 
-        assert_eq!(Err(CastError), bool::from_lit(int_lit));
-        assert_eq!(Err(CastError), bool::from_lit(str_lit));
-        assert_eq!(Err(CastError), bool::from_lit(float_lit));
-        assert_eq!(Ok(false), bool::from_lit(bool_lit));
+        assert!(bool::from_lit(int_lit).is_err());
+        assert!(bool::from_lit(str_lit).is_err());
+        assert!(bool::from_lit(float_lit).is_err());
+        assert!(!bool::from_lit(bool_lit).unwrap());
+    }
+
+    #[test]
+    fn test_char() {
+        let char_lit: Lit = parse_quote!('a');
+        let str_lit: Lit = parse_quote!("str");
+
+        assert_eq!(char::from_lit(char_lit).unwrap(), 'a');
+        assert!(char::from_lit(str_lit).is_err());
+    }
+
+    #[test]
+    fn test_byte_str() {
+        let byte_str_lit: Lit = parse_quote!(b"bytes");
+        let str_lit: Lit = parse_quote!("str");
+
+        assert_eq!(Vec::<u8>::from_lit(byte_str_lit).unwrap(), b"bytes");
+        assert!(Vec::<u8>::from_lit(str_lit).is_err());
+    }
+
+    #[test]
+    fn test_to_lit() {
+        let int_lit: Lit = parse_quote!(12);
+        let float_lit: Lit = parse_quote!(12.1);
+        let bool_lit: Lit = parse_quote!(false);
+        let str_lit: Lit = parse_quote!("str");
+
+        assert_eq!(12u64.to_lit(), int_lit);
+        assert_eq!(12.1f64.to_lit(), float_lit);
+        assert_eq!(false.to_lit(), bool_lit);
+        assert_eq!("str".to_string().to_lit(), str_lit);
+        assert_eq!("str".to_lit(), str_lit);
     }
 }
@@ -0,0 +1,153 @@
+//! Structured parsing of `#[repr(...)]` clauses, so derive authors can
+//! inspect an item's layout (e.g. to reject `packed` or require
+//! `#[repr(C)]`) without matching raw meta trees by hand.
+
+use syn::{AttrStyle, Attribute, LitInt, Meta};
+
+use crate::iter_meta_list;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReprAttr {
+    C,
+    Transparent,
+    Packed(Option<u64>),
+    Align(u64),
+    Simd,
+    Int(IntType),
+}
+
+/// Parses every `#[repr(...)]` attribute in `attrs`, accumulating all of
+/// their comma-separated items (across repeated `#[repr(..)]` attributes
+/// too) into a single `Vec`.
+pub fn get_repr(attrs: &[Attribute]) -> Vec<ReprAttr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer && attr.path().is_ident("repr"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(meta_list) => iter_meta_list(meta_list, |iter| {
+                iter.filter_map(repr_item).collect::<Vec<_>>()
+            })
+            .ok(),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+fn repr_item(meta: &Meta) -> Option<ReprAttr> {
+    match meta {
+        Meta::Path(path) => {
+            let ident = path.get_ident()?.to_string();
+            if let Some(int_type) = int_type_from_ident(&ident) {
+                return Some(ReprAttr::Int(int_type));
+            }
+            match ident.as_str() {
+                "C" => Some(ReprAttr::C),
+                "transparent" => Some(ReprAttr::Transparent),
+                "packed" => Some(ReprAttr::Packed(None)),
+                "simd" => Some(ReprAttr::Simd),
+                _ => None,
+            }
+        }
+        Meta::List(meta_list) => {
+            let ident = meta_list.path.get_ident()?.to_string();
+            let arg = meta_list
+                .parse_args::<LitInt>()
+                .ok()?
+                .base10_parse::<u64>()
+                .ok()?;
+            match ident.as_str() {
+                "packed" => Some(ReprAttr::Packed(Some(arg))),
+                "align" => Some(ReprAttr::Align(arg)),
+                _ => None,
+            }
+        }
+        Meta::NameValue(..) => None,
+    }
+}
+
+fn int_type_from_ident(ident: &str) -> Option<IntType> {
+    Some(match ident {
+        "i8" => IntType::I8,
+        "i16" => IntType::I16,
+        "i32" => IntType::I32,
+        "i64" => IntType::I64,
+        "i128" => IntType::I128,
+        "isize" => IntType::Isize,
+        "u8" => IntType::U8,
+        "u16" => IntType::U16,
+        "u32" => IntType::U32,
+        "u64" => IntType::U64,
+        "u128" => IntType::U128,
+        "usize" => IntType::Usize,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_get_repr_bare_variants() {
+        let attr: Attribute = parse_quote!(#[repr(C)]);
+        assert_eq!(get_repr(&[attr]), vec![ReprAttr::C]);
+
+        let attr: Attribute = parse_quote!(#[repr(transparent)]);
+        assert_eq!(get_repr(&[attr]), vec![ReprAttr::Transparent]);
+
+        let attr: Attribute = parse_quote!(#[repr(simd)]);
+        assert_eq!(get_repr(&[attr]), vec![ReprAttr::Simd]);
+
+        let attr: Attribute = parse_quote!(#[repr(packed)]);
+        assert_eq!(get_repr(&[attr]), vec![ReprAttr::Packed(None)]);
+
+        let attr: Attribute = parse_quote!(#[repr(u8)]);
+        assert_eq!(get_repr(&[attr]), vec![ReprAttr::Int(IntType::U8)]);
+    }
+
+    #[test]
+    fn test_get_repr_with_args() {
+        let attr: Attribute = parse_quote!(#[repr(packed(2))]);
+        assert_eq!(get_repr(&[attr]), vec![ReprAttr::Packed(Some(2))]);
+
+        let attr: Attribute = parse_quote!(#[repr(align(4))]);
+        assert_eq!(get_repr(&[attr]), vec![ReprAttr::Align(4)]);
+    }
+
+    #[test]
+    fn test_get_repr_multiple_items_and_attrs() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[repr(C, align(4))]),
+            parse_quote!(#[repr(packed(1))]),
+        ];
+
+        assert_eq!(
+            get_repr(&attrs),
+            vec![ReprAttr::C, ReprAttr::Align(4), ReprAttr::Packed(Some(1)),]
+        );
+    }
+
+    #[test]
+    fn test_get_repr_ignores_unrelated_attrs() {
+        let attr: Attribute = parse_quote!(#[derive(Debug)]);
+        assert_eq!(get_repr(&[attr]), vec![]);
+    }
+}